@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::types::{RepoMethod, Result};
+
+/// A declarative manifest of targets to mirror in one run, loaded via `--config`.
+#[derive(Deserialize, Debug)]
+pub struct Config {
+    pub targets: Vec<Target>,
+}
+
+/// One org/user to enumerate and clone into its own directory.
+#[derive(Deserialize, Debug)]
+pub struct Target {
+    /// GitHub org or user to enumerate repos for
+    pub org: String,
+
+    /// Directory to clone this target's repos into
+    pub output_dir: String,
+
+    #[serde(default)]
+    pub method: TargetMethod,
+
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetMethod {
+    #[default]
+    Ssh,
+    Https,
+}
+
+impl From<&TargetMethod> for RepoMethod {
+    fn from(value: &TargetMethod) -> Self {
+        match value {
+            TargetMethod::Ssh => RepoMethod::Ssh,
+            TargetMethod::Https => RepoMethod::Https,
+        }
+    }
+}
+
+/// Loads and parses a target manifest from a TOML file at `path`.
+pub fn load(path: &Path) -> Result<Config> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(toml::from_str(&contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_parses_multiple_targets() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("github-fetch-repos-test-config.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[targets]]
+            org = "chimbosonic"
+            output_dir = "chimbosonic-repos"
+            exclude = ["-old"]
+
+            [[targets]]
+            org = "some-user"
+            output_dir = "some-user-repos"
+            method = "https"
+            include = ["service-"]
+            "#,
+        )
+        .unwrap();
+
+        let config = load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.targets.len(), 2);
+        assert_eq!(config.targets[0].org, "chimbosonic");
+        assert_eq!(config.targets[0].exclude, vec!["-old".to_string()]);
+        assert_eq!(config.targets[1].method, TargetMethod::Https);
+        assert_eq!(config.targets[1].include, vec!["service-".to_string()]);
+    }
+}
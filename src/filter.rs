@@ -0,0 +1,128 @@
+use glob::Pattern as GlobPattern;
+use regex::Regex;
+
+use crate::types::Repo;
+
+/// A single filter rule, parsed as a `/regex/`-delimited regular expression, then a shell
+/// glob (`foo-*`), then a literal substring, tried in that order.
+enum FilterPattern {
+    Literal(String),
+    Glob(GlobPattern),
+    Regex(Regex),
+}
+
+impl FilterPattern {
+    fn parse(raw: &str) -> Self {
+        if let Some(inner) = raw.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            if let Ok(regex) = Regex::new(inner) {
+                return FilterPattern::Regex(regex);
+            }
+        }
+
+        if raw.contains(['*', '?', '[']) {
+            if let Ok(glob) = GlobPattern::new(&raw.to_lowercase()) {
+                return FilterPattern::Glob(glob);
+            }
+        }
+
+        FilterPattern::Literal(raw.to_lowercase())
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            FilterPattern::Literal(pattern) => name.to_lowercase().contains(pattern.as_str()),
+            FilterPattern::Glob(pattern) => pattern.matches(&name.to_lowercase()),
+            FilterPattern::Regex(pattern) => pattern.is_match(name),
+        }
+    }
+}
+
+/// Include/exclude filter rules matched against `repo.name`. When `include` is non-empty it
+/// acts as an allowlist; `exclude` then subtracts from whatever passed it (or from everything,
+/// when no includes were given, matching the crate's original exclude-only behavior).
+pub struct FilterSpec {
+    include: Vec<FilterPattern>,
+    exclude: Vec<FilterPattern>,
+}
+
+impl FilterSpec {
+    pub fn new(include: &[String], exclude: &[String]) -> Self {
+        Self {
+            include: include.iter().map(|p| FilterPattern::parse(p)).collect(),
+            exclude: exclude.iter().map(|p| FilterPattern::parse(p)).collect(),
+        }
+    }
+
+    pub fn matches(&self, repo: &Repo) -> bool {
+        let included =
+            self.include.is_empty() || self.include.iter().any(|p| p.matches(&repo.name));
+        let excluded = self.exclude.iter().any(|p| p.matches(&repo.name));
+
+        included && !excluded
+    }
+}
+
+/// Keeps only the repos that pass `include`/`exclude`. See [`FilterSpec`] for matching rules.
+pub fn filter_repos(repos: Vec<Repo>, include: &[String], exclude: &[String]) -> Vec<Repo> {
+    let spec = FilterSpec::new(include, exclude);
+    repos.into_iter().filter(|repo| spec.matches(repo)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RepoMethod;
+
+    fn repo(name: &str) -> Repo {
+        Repo {
+            ssh_url: format!("git@github.com:chimbosonic/{name}.git"),
+            https_url: format!("https://github.com/chimbosonic/{name}.git"),
+            name: name.to_string(),
+            method: RepoMethod::Ssh,
+        }
+    }
+
+    #[test]
+    fn test_literal_exclude_is_case_insensitive_substring() {
+        let repos = vec![repo("Github-fetch-repos"), repo("cli-kneeboard")];
+        let filtered = filter_repos(repos, &[], &["github-fetch-repos".to_string()]);
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "cli-kneeboard");
+    }
+
+    #[test]
+    fn test_glob_exclude() {
+        let repos = vec![repo("service-old"), repo("service-api")];
+        let filtered = filter_repos(repos, &[], &["*-old".to_string()]);
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "service-api");
+    }
+
+    #[test]
+    fn test_regex_exclude() {
+        let repos = vec![repo("service-api"), repo("cli-kneeboard")];
+        let filtered = filter_repos(repos, &[], &["/^service-/".to_string()]);
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "cli-kneeboard");
+    }
+
+    #[test]
+    fn test_include_acts_as_allowlist_then_exclude_subtracts() {
+        let repos = vec![
+            repo("service-api"),
+            repo("service-old"),
+            repo("cli-kneeboard"),
+        ];
+        let filtered = filter_repos(
+            repos,
+            &["service-*".to_string()],
+            &["*-old".to_string()],
+        );
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "service-api");
+    }
+}
@@ -0,0 +1,110 @@
+use std::cell::Cell;
+
+use git2::build::RepoBuilder;
+use git2::{Cred, CredentialType, FetchOptions, RemoteCallbacks, Repository};
+use indicatif::ProgressBar;
+
+use crate::types::{RepoMethod, Result};
+
+const SSH_KEY_NAMES: [&str; 2] = ["id_ed25519", "id_rsa"];
+
+/// Tries the ssh-agent first, then falls back to `~/.ssh/id_ed25519`/`id_rsa`. Tracks the
+/// attempt count so that once both have been tried we give up instead of handing libgit2 the
+/// same failing credential forever.
+fn ssh_credentials(
+    username_from_url: Option<&str>,
+    allowed_types: CredentialType,
+    attempts: &Cell<u32>,
+) -> std::result::Result<Cred, git2::Error> {
+    let username = username_from_url.unwrap_or("git");
+    let attempt = attempts.get();
+    attempts.set(attempt + 1);
+
+    if attempt == 0 {
+        if let Ok(cred) = Cred::ssh_key_from_agent(username) {
+            return Ok(cred);
+        }
+    }
+
+    if attempt <= 1 && allowed_types.contains(CredentialType::SSH_KEY) {
+        if let Some(home) = std::env::var_os("HOME") {
+            for key_name in SSH_KEY_NAMES {
+                let private_key = std::path::Path::new(&home).join(".ssh").join(key_name);
+                if private_key.exists() {
+                    if let Ok(cred) = Cred::ssh_key(username, None, &private_key, None) {
+                        return Ok(cred);
+                    }
+                }
+            }
+        }
+    }
+
+    Err(git2::Error::from_str(
+        "no usable ssh credentials found (tried ssh-agent and ~/.ssh keys)",
+    ))
+}
+
+/// Builds the `RemoteCallbacks` used for both clone and fetch, wiring credentials for the
+/// repo's `RepoMethod` and forwarding `transfer_progress` into `pb`.
+fn remote_callbacks<'a>(method: &'a RepoMethod, pb: &'a ProgressBar) -> RemoteCallbacks<'a> {
+    let mut callbacks = RemoteCallbacks::new();
+
+    match method {
+        RepoMethod::Ssh => {
+            let attempts = Cell::new(0u32);
+            callbacks.credentials(move |_url, username_from_url, allowed_types| {
+                ssh_credentials(username_from_url, allowed_types, &attempts)
+            });
+        }
+        RepoMethod::Https => {
+            callbacks.credentials(|_url, _username_from_url, _allowed_types| {
+                let token = std::env::var("GITHUB_TOKEN")
+                    .or_else(|_| std::env::var("GH_TOKEN"))
+                    .unwrap_or_default();
+                Cred::userpass_plaintext("x-access-token", &token)
+            });
+        }
+    }
+
+    callbacks.transfer_progress(|stats| {
+        let total = stats.total_objects() as u64;
+        let received = stats.received_objects() as u64;
+        if pb.length() != Some(total) {
+            pb.set_length(total);
+        }
+        pb.set_position(received);
+        true
+    });
+
+    callbacks
+}
+
+/// Clones `url` into `path` using libgit2, reporting progress on `pb`.
+pub fn clone(url: &str, path: &std::path::Path, method: &RepoMethod, pb: &ProgressBar) -> Result<()> {
+    let callbacks = remote_callbacks(method, pb);
+
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(callbacks);
+
+    RepoBuilder::new().fetch_options(fetch_options).clone(url, path)?;
+
+    pb.finish_with_message(format!("{} cloned", path.display()));
+    Ok(())
+}
+
+/// Opens the repo at `path` and fetches all branches from `origin` via libgit2, reporting
+/// progress on `pb`.
+pub fn fetch(path: &std::path::Path, method: &RepoMethod, pb: &ProgressBar) -> Result<()> {
+    let repo = Repository::open(path)?;
+    let mut remote = repo.find_remote("origin")?;
+
+    let callbacks = remote_callbacks(method, pb);
+
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(callbacks);
+
+    remote.fetch(&["+refs/heads/*:refs/remotes/origin/*"], Some(&mut fetch_options), None)?;
+
+    pb.finish_with_message(format!("{} fetched", path.display()));
+    Ok(())
+}
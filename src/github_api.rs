@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use serde::Deserialize;
+
+use crate::types::{Repo, RepoMethod, Result};
+
+const PER_PAGE: u32 = 100;
+
+/// Shape of a single entry in the REST API's repo list responses (e.g.
+/// `GET /orgs/{org}/repos`), trimmed down to the fields we actually use. Deliberately distinct
+/// from `GHOuput`, which mirrors `gh repo list --json`'s differently-named/shaped output.
+#[derive(Deserialize, Debug)]
+struct ApiRepo {
+    ssh_url: String,
+    clone_url: String,
+}
+
+impl TryFrom<ApiRepo> for Repo {
+    type Error = crate::types::Error;
+
+    fn try_from(value: ApiRepo) -> Result<Repo> {
+        let name = crate::get_repo_name(&value.ssh_url)?;
+
+        Ok(Repo {
+            ssh_url: value.ssh_url,
+            https_url: value.clone_url,
+            name,
+            method: RepoMethod::Ssh,
+        })
+    }
+}
+
+/// Fetches every repo for an org (falling back to a user account) via the GitHub REST API,
+/// following `Link: rel="next"` pagination headers so orgs with more than 1000 repos are
+/// still enumerated in full.
+pub async fn get_org_repos(org: &str) -> Result<Vec<Repo>> {
+    let client = build_client()?;
+    let token = std::env::var("GITHUB_TOKEN")
+        .or_else(|_| std::env::var("GH_TOKEN"))
+        .ok();
+
+    let mut repos = Vec::new();
+    let mut url = format!(
+        "https://api.github.com/orgs/{org}/repos?per_page={PER_PAGE}&page=1"
+    );
+    let mut tried_user_fallback = false;
+
+    loop {
+        let mut request = client.get(&url);
+        if let Some(token) = &token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND && !tried_user_fallback {
+            tried_user_fallback = true;
+            url = format!("https://api.github.com/users/{org}/repos?per_page={PER_PAGE}&page=1");
+            continue;
+        }
+
+        if wait_for_rate_limit(response.headers()).await {
+            // We just slept past the reset window; re-issue the same request instead of
+            // surfacing the rate-limited response as a failure.
+            continue;
+        }
+
+        if !response.status().is_success() {
+            return Err(format!("GitHub API request failed with status {}", response.status()).into());
+        }
+
+        let next = next_page_url(response.headers());
+        let page: Vec<ApiRepo> = response.json().await?;
+        let mut page: Vec<Repo> = page
+            .into_iter()
+            .map(Repo::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        repos.append(&mut page);
+
+        match next {
+            Some(next_url) => url = next_url,
+            None => break,
+        }
+    }
+
+    Ok(repos)
+}
+
+fn build_client() -> Result<Client> {
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static("github-fetch-repos"));
+    Ok(Client::builder().default_headers(headers).build()?)
+}
+
+/// Parses the `next` link out of a GitHub `Link` response header.
+fn next_page_url(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get("link")?.to_str().ok()?;
+
+    link.split(',').find_map(|part| {
+        let mut segments = part.split(';');
+        let url = segments.next()?.trim();
+        let rel_is_next = segments.any(|s| s.trim() == "rel=\"next\"");
+
+        if rel_is_next {
+            Some(url.trim_start_matches('<').trim_end_matches('>').to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Sleeps until the rate limit resets if the remaining budget has hit zero. Returns `true` if
+/// it slept, so the caller knows to retry the request that triggered this instead of treating
+/// its (likely non-success) response as a final failure.
+async fn wait_for_rate_limit(headers: &HeaderMap) -> bool {
+    let remaining: Option<u64> = headers
+        .get("x-ratelimit-remaining")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+
+    if remaining != Some(0) {
+        return false;
+    }
+
+    let reset: Option<u64> = headers
+        .get("x-ratelimit-reset")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+
+    let Some(reset) = reset else {
+        return false;
+    };
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let wait_secs = reset.saturating_sub(now);
+    if wait_secs == 0 {
+        return false;
+    }
+
+    println!("⏳ Rate limit exhausted, sleeping for {wait_secs}s until reset...");
+    tokio::time::sleep(Duration::from_secs(wait_secs)).await;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_next_page_url_present() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "link",
+            HeaderValue::from_static(
+                "<https://api.github.com/orgs/foo/repos?page=2>; rel=\"next\", <https://api.github.com/orgs/foo/repos?page=5>; rel=\"last\"",
+            ),
+        );
+
+        assert_eq!(
+            next_page_url(&headers),
+            Some("https://api.github.com/orgs/foo/repos?page=2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_api_repo_response_maps_ssh_and_clone_url_into_repo() {
+        let body = r#"[
+            {
+                "id": 1,
+                "name": "example-repo",
+                "full_name": "acme/example-repo",
+                "html_url": "https://github.com/acme/example-repo",
+                "ssh_url": "git@github.com:acme/example-repo.git",
+                "clone_url": "https://github.com/acme/example-repo.git"
+            }
+        ]"#;
+
+        let api_repos: Vec<ApiRepo> = serde_json::from_str(body).unwrap();
+        let repos: Vec<Repo> = api_repos
+            .into_iter()
+            .map(Repo::try_from)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].name, "example-repo");
+        assert_eq!(repos[0].ssh_url, "git@github.com:acme/example-repo.git");
+        assert_eq!(
+            repos[0].https_url,
+            "https://github.com/acme/example-repo.git"
+        );
+        assert_eq!(repos[0].method, RepoMethod::Ssh);
+    }
+
+    #[test]
+    fn test_next_page_url_absent_on_last_page() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "link",
+            HeaderValue::from_static(
+                "<https://api.github.com/orgs/foo/repos?page=1>; rel=\"prev\"",
+            ),
+        );
+
+        assert_eq!(next_page_url(&headers), None);
+    }
+}
@@ -1,31 +1,124 @@
 use clap::Parser;
 use futures::stream::{self, StreamExt};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use std::path::Path;
 use std::sync::{
     Arc,
     atomic::{AtomicUsize, Ordering},
 };
-use tokio::process::Command;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::process::Command as ShellCommand;
 use tokio::sync::Semaphore;
 
+mod config;
+mod filter;
+mod git_ops;
+mod github_api;
+mod store;
 mod types;
+mod webhook;
+use crate::store::{Action, RepoResult, Store};
 use crate::types::*;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
+    if let Some(Command::Serve { address, secrets }) = &args.command {
+        return webhook::serve(address, secrets, args.max_threads).await;
+    }
+
+    if args.max_threads >= 10 {
+        return Err("Please use less than 10 threads".into());
+    }
+
+    let store = args.db.as_deref().map(Store::open).transpose()?.map(Arc::new);
+
+    if let Some(config_path) = &args.config {
+        return run_from_config(config_path, &args, store).await;
+    }
+
     println!("🔍 Fetching list of repos...");
-    let repos = get_list_of_repos(&args.github_org).await?;
-    let mut repos = filter_repos(repos, args.filters);
+    let repos = get_list_of_repos(&args.github_org, args.api).await?;
+    let mut repos = filter::filter_repos(
+        repos,
+        args.include.as_deref().unwrap_or(&[]),
+        args.exclude.as_deref().unwrap_or(&[]),
+    );
     if args.https {
         repos
             .iter_mut()
             .for_each(|repo| repo.method = RepoMethod::Https);
     }
+
+    process_repos(
+        repos,
+        args.max_threads,
+        args.dry_run,
+        store,
+        args.report_failures_only,
+        std::path::PathBuf::from("."),
+    )
+    .await
+}
+
+/// Enumerates and clones/fetches every target in `config_path`'s manifest, each into its own
+/// `output_dir`, with CLI flags (`--https`, `--filters`, `--max-threads`) layered on top.
+async fn run_from_config(
+    config_path: &std::path::Path,
+    args: &Args,
+    store: Option<Arc<Store>>,
+) -> Result<()> {
+    let manifest = config::load(config_path)?;
+
+    for target in manifest.targets {
+        println!("🎯 Target [{}] -> {}", target.org, target.output_dir);
+
+        std::fs::create_dir_all(&target.output_dir)?;
+
+        let repos = get_list_of_repos(&target.org, args.api).await?;
+
+        let mut include = target.include.clone();
+        include.extend(args.include.iter().flatten().cloned());
+        let mut exclude = target.exclude.clone();
+        exclude.extend(args.exclude.iter().flatten().cloned());
+        let mut repos = filter::filter_repos(repos, &include, &exclude);
+
+        if args.https || target.method == config::TargetMethod::Https {
+            repos
+                .iter_mut()
+                .for_each(|repo| repo.method = RepoMethod::Https);
+        }
+
+        process_repos(
+            repos,
+            args.max_threads,
+            args.dry_run,
+            store.clone(),
+            args.report_failures_only,
+            std::path::PathBuf::from(&target.output_dir),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Clones/fetches `repos` with up to `max_threads` concurrent jobs, printing interleaved
+/// progress bars. In `dry_run` mode, only lists what would be processed. Records each
+/// outcome in `store` (if given) and prints a grouped end-of-run report, returning an error
+/// (so the process exits non-zero) if any repo failed.
+async fn process_repos(
+    repos: Vec<Repo>,
+    max_threads: usize,
+    dry_run: bool,
+    store: Option<Arc<Store>>,
+    report_failures_only: bool,
+    base_dir: std::path::PathBuf,
+) -> Result<()> {
     let total = repos.len();
 
-    if args.dry_run {
+    if dry_run {
         println!("Dry run mode enabled. The following repositories would be processed:");
         for repo in &repos {
             println!(" - {}: {}", repo.name, repo.url());
@@ -34,52 +127,113 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    let max_threads: usize = args.max_threads;
-    if max_threads >= 10 {
-        return Err("Please use less than 10 threads".into());
-    };
-
     let done_counter = Arc::new(AtomicUsize::new(0));
     let semaphore = Arc::new(Semaphore::new(max_threads));
+    let multi_progress = Arc::new(MultiProgress::new());
+    let run_started_at = Arc::new(run_timestamp());
+    let base_dir = Arc::new(base_dir);
 
     println!("🚀 Starting to process {total} repos with max {max_threads} concurrent jobs...");
 
-    stream::iter(
-        repos
-            .into_iter()
-            .map(|repo| process_repo(semaphore.clone(), done_counter.clone(), repo, total)),
-    )
+    let results = stream::iter(repos.into_iter().map(|repo| {
+        process_repo(
+            semaphore.clone(),
+            done_counter.clone(),
+            multi_progress.clone(),
+            store.clone(),
+            run_started_at.clone(),
+            base_dir.clone(),
+            repo,
+            total,
+        )
+    }))
     .buffer_unordered(max_threads)
     .collect::<Vec<_>>()
     .await;
 
     println!("🎉 All {total} repos processed!");
+    store::print_summary(&results, report_failures_only);
+
+    if results.iter().any(|result| !result.success) {
+        return Err("One or more repos failed to sync".into());
+    }
+
     Ok(())
 }
 
-async fn process_repo(
+fn run_timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    secs.to_string()
+}
+
+pub(crate) async fn process_repo(
     semaphore: Arc<Semaphore>,
     done_counter: Arc<AtomicUsize>,
+    multi_progress: Arc<MultiProgress>,
+    store: Option<Arc<Store>>,
+    run_started_at: Arc<String>,
+    base_dir: Arc<std::path::PathBuf>,
     repo: Repo,
     repo_total: usize,
-) -> () {
+) -> RepoResult {
     let _permit = semaphore.acquire().await.unwrap();
 
     let name = &repo.name;
+    let path = base_dir.join(name);
+    let pb = multi_progress.add(new_progress_bar(name));
+    let started = Instant::now();
 
-    if Path::new(name).exists() {
-        println!("[{name}] already exists, fetching...");
-        let _ = repo.fetch().await.map_err(|err| println!("{err}"));
+    let action = if path.exists() {
+        Action::Fetch
     } else {
-        println!("Cloning [{name}]...");
-        let _ = repo.clone().await.map_err(|err| println!("{err}"));
+        Action::Clone
+    };
+
+    let outcome = match action {
+        Action::Fetch => repo.fetch(&pb, &path).await,
+        Action::Clone => repo.clone(&pb, &path).await,
+    };
+
+    if let Err(err) = &outcome {
+        println!("{err}");
+    }
+
+    let result = RepoResult {
+        name: repo.name.clone(),
+        url: repo.url(),
+        action,
+        success: outcome.is_ok(),
+        error: outcome.err().map(|err| err.to_string()),
+        duration: started.elapsed(),
+    };
+
+    if let Some(store) = &store {
+        if let Err(err) = store.record(&run_started_at, &result) {
+            println!("Failed to record result for {}: {err}", result.name);
+        }
     }
 
     let finished = done_counter.fetch_add(1, Ordering::SeqCst) + 1;
     println!("✅ [{finished}/{repo_total}] Finished {name}");
+
+    result
+}
+
+fn new_progress_bar(name: &str) -> ProgressBar {
+    let pb = ProgressBar::new(0);
+    pb.set_style(
+        ProgressStyle::with_template("{prefix} [{bar:30}] {pos}/{len} objects")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    pb.set_prefix(name.to_string());
+    pb
 }
 
-fn get_repo_name(ssh_url: &RepoSshUrl) -> Result<String> {
+pub(crate) fn get_repo_name(ssh_url: &RepoSshUrl) -> Result<String> {
     Ok(ssh_url
         .split('/')
         .next_back()
@@ -96,40 +250,30 @@ impl Repo {
         }
     }
 
-    async fn fetch(&self) -> Result<()> {
-        let name = &self.name;
-        let output = Command::new("git")
-            .args(["-C", name, "fetch", "--all"])
-            .status()
-            .await;
-
-        match output {
-            Ok(status) if status.success() => Ok(()),
-            Ok(status) => {
-                Err(format!("git fetch failed for {name} (code: {:?})", status.code()).into())
-            }
-            Err(err) => Err(format!("failed to run git fetch for {name}: {err}").into()),
-        }
-    }
+    async fn fetch(&self, pb: &ProgressBar, path: &Path) -> Result<()> {
+        let path = path.to_path_buf();
+        let method = self.method.clone();
+        let pb = pb.clone();
 
-    async fn clone(&self) -> Result<()> {
-        let name = &self.name;
-        let url = &self.url();
+        tokio::task::spawn_blocking(move || git_ops::fetch(&path, &method, &pb)).await?
+    }
 
-        let output = Command::new("git").args(["clone", url]).status().await;
+    async fn clone(&self, pb: &ProgressBar, path: &Path) -> Result<()> {
+        let path = path.to_path_buf();
+        let url = self.url();
+        let method = self.method.clone();
+        let pb = pb.clone();
 
-        match output {
-            Ok(status) if status.success() => Ok(()),
-            Ok(status) => {
-                Err(format!("git clone failed for {name} (code: {:?})", status.code()).into())
-            }
-            Err(err) => Err(format!("failed to run git clone for {name}: {err}").into()),
-        }
+        tokio::task::spawn_blocking(move || git_ops::clone(&url, &path, &method, &pb)).await?
     }
 }
 
-async fn get_list_of_repos(github_org: &str) -> Result<Vec<Repo>> {
-    let output = match Command::new("gh")
+async fn get_list_of_repos(github_org: &str, use_api: bool) -> Result<Vec<Repo>> {
+    if use_api {
+        return github_api::get_org_repos(github_org).await;
+    }
+
+    let output = match ShellCommand::new("gh")
         .args([
             "repo", "list", github_org, "--json", "sshUrl", "--json", "url", "-L", "1000",
         ])
@@ -169,28 +313,5 @@ fn parse_gh_output(output: &[u8]) -> Result<Vec<Repo>> {
     repos.iter().map(Repo::try_from).collect()
 }
 
-fn filter_repos(repos: Vec<Repo>, filters: Option<Vec<String>>) -> Vec<Repo> {
-    if let Some(custom_filters) = filters {
-        return repos
-            .into_iter()
-            .filter(|repo| !check_filter(repo, &custom_filters))
-            .collect();
-    }
-
-    repos
-}
-
-fn check_filter(repo: &Repo, filters: &Vec<String>) -> bool {
-    let name = repo.name.to_lowercase();
-    for filter in filters {
-        let filter = filter.to_lowercase();
-
-        if name.contains(&filter) {
-            return true;
-        }
-    }
-    false
-}
-
 #[cfg(test)]
 mod tests;
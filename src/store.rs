@@ -0,0 +1,145 @@
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rusqlite::{params, Connection};
+
+use crate::types::Result;
+
+/// The action taken for a repo during a run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Clone,
+    Fetch,
+}
+
+impl Action {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Action::Clone => "clone",
+            Action::Fetch => "fetch",
+        }
+    }
+}
+
+/// The outcome of processing a single repo, ready to be persisted and summarised.
+pub struct RepoResult {
+    pub name: String,
+    pub url: String,
+    pub action: Action,
+    pub success: bool,
+    pub error: Option<String>,
+    pub duration: Duration,
+}
+
+/// A SQLite-backed record of every repo processed across runs, one row per repo per run.
+/// Rows are only written; the end-of-run report is printed from the in-memory results.
+pub struct Store {
+    conn: Mutex<Connection>,
+}
+
+impl Store {
+    /// Opens (creating if needed) the SQLite database at `path` and ensures the schema exists.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS repo_runs (
+                id              INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_started_at  TEXT NOT NULL,
+                name            TEXT NOT NULL,
+                url             TEXT NOT NULL,
+                action          TEXT NOT NULL,
+                success         INTEGER NOT NULL,
+                error           TEXT,
+                duration_ms     INTEGER NOT NULL
+            )",
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Records one repo's outcome for `run_started_at`.
+    pub fn record(&self, run_started_at: &str, result: &RepoResult) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO repo_runs (run_started_at, name, url, action, success, error, duration_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run_started_at,
+                result.name,
+                result.url,
+                result.action.as_str(),
+                result.success,
+                result.error,
+                result.duration.as_millis() as i64,
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+/// Prints a grouped end-of-run summary and, when `failures_only` is set, restricts the
+/// per-repo listing to just the repos that failed.
+pub fn print_summary(results: &[RepoResult], failures_only: bool) {
+    let cloned = results
+        .iter()
+        .filter(|r| r.success && r.action == Action::Clone)
+        .count();
+    let fetched = results
+        .iter()
+        .filter(|r| r.success && r.action == Action::Fetch)
+        .count();
+    let failed: Vec<&RepoResult> = results.iter().filter(|r| !r.success).collect();
+
+    println!(
+        "📊 Summary: {cloned} cloned, {fetched} fetched, {} failed",
+        failed.len()
+    );
+
+    if failures_only {
+        for result in &failed {
+            println!(" - {}: {}", result.name, result.error.as_deref().unwrap_or("unknown error"));
+        }
+        return;
+    }
+
+    for result in results {
+        let icon = if result.success { "✅" } else { "❌" };
+        match &result.error {
+            Some(error) => println!(" {icon} [{}] {}: {error}", result.action.as_str(), result.name),
+            None => println!(" {icon} [{}] {}", result.action.as_str(), result.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, action: Action, success: bool) -> RepoResult {
+        RepoResult {
+            name: name.to_string(),
+            url: format!("git@github.com:chimbosonic/{name}.git"),
+            action,
+            success,
+            error: if success { None } else { Some("boom".to_string()) },
+            duration: Duration::from_secs(1),
+        }
+    }
+
+    #[test]
+    fn test_record_persists_a_row() {
+        let store = Store::open(Path::new(":memory:")).unwrap();
+        let outcome = result("cli-kneeboard", Action::Clone, true);
+
+        store.record("2026-07-29T00:00:00Z", &outcome).unwrap();
+
+        let conn = store.conn.lock().unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM repo_runs", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
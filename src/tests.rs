@@ -1,20 +1,5 @@
 use super::*;
 
-#[test]
-fn test_check_filter() {
-    let repo = Repo {
-        ssh_url: "git@github.com:chimbosonic/Github-fetch-repos.git".to_string(),
-        https_url: "https://github.com/chimbosonic/Github-fetch-repos.git".to_string(),
-        name: "Github-fetch-repos".to_string(),
-        method: RepoMethod::Ssh,
-    };
-
-    assert_eq!(
-        check_filter(&repo, &vec!["github-fetch-repos".to_string()]),
-        true
-    );
-}
-
 #[test]
 fn test_get_repo_name() {
     let repo_ssh_url: RepoSshUrl =
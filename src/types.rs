@@ -1,4 +1,6 @@
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -16,7 +18,7 @@ pub struct Repo {
     pub method: RepoMethod,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum RepoMethod {
     Ssh,
     Https,
@@ -44,16 +46,71 @@ pub struct Args {
     pub dry_run: bool,
 
     #[arg(
-        short,
-        long,
-        help = "List of repo name filters to exclude",
+        short = 'f',
+        long = "exclude",
+        visible_alias = "filters",
+        help = "List of repo name filters to exclude: literal substrings, shell globs (foo-*), or /regex/",
+        value_delimiter = ','
+    )]
+    pub exclude: Option<Vec<String>>,
+
+    #[arg(
+        long = "include",
+        help = "List of repo name filters to include (acts as an allowlist when set): literal substrings, shell globs (foo-*), or /regex/",
         value_delimiter = ','
     )]
-    pub filters: Option<Vec<String>>,
+    pub include: Option<Vec<String>>,
 
     #[arg(short, long, default_value = "5", help = "Max Thread Count (10 max)")]
     pub max_threads: usize,
 
     #[arg(long, help = "Use https rather than ssh to fetch repos")]
     pub https: bool,
+
+    #[arg(
+        long,
+        help = "Talk to the GitHub REST API directly instead of shelling out to `gh` (no 1000-repo cap, honours GITHUB_TOKEN/GH_TOKEN)"
+    )]
+    pub api: bool,
+
+    #[arg(
+        long,
+        help = "Path to a TOML manifest describing multiple orgs/users to mirror in one run, each into its own directory"
+    )]
+    pub config: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Path to a SQLite database to record one row per repo per run (created if missing)"
+    )]
+    pub db: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Only list failed repos in the end-of-run report, instead of every repo processed"
+    )]
+    pub report_failures_only: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run a long-lived daemon that listens for GitHub push webhooks and keeps the
+    /// corresponding repos cloned/fetched, instead of running the batch sync once
+    Serve {
+        #[arg(
+            long,
+            default_value = "0.0.0.0:8080",
+            help = "Address to bind the webhook listener to"
+        )]
+        address: String,
+
+        #[arg(
+            long,
+            help = "Path to a TOML file listing the pre-shared webhook secrets to accept"
+        )]
+        secrets: PathBuf,
+    },
 }
@@ -0,0 +1,198 @@
+use std::path::Path;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use hmac::{Hmac, Mac};
+use indicatif::MultiProgress;
+use serde::Deserialize;
+use sha2::Sha256;
+use tokio::sync::Semaphore;
+
+use crate::process_repo;
+use crate::types::{GHOuput, Repo, Result};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Pre-shared webhook secrets loaded from a TOML config, e.g.:
+///
+/// ```toml
+/// [[webhooks]]
+/// name = "my-org"
+/// secret = "correct-horse-battery-staple"
+/// ```
+#[derive(Deserialize, Debug)]
+struct SecretsConfig {
+    webhooks: Vec<WebhookSecret>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WebhookSecret {
+    #[allow(dead_code, reason = "kept for operators labelling entries in the config file")]
+    name: String,
+    secret: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct PushEvent {
+    repository: PushRepository,
+}
+
+#[derive(Deserialize, Debug)]
+struct PushRepository {
+    ssh_url: String,
+    clone_url: String,
+}
+
+#[derive(Clone)]
+struct AppState {
+    secrets: Arc<Vec<String>>,
+    semaphore: Arc<Semaphore>,
+}
+
+/// Runs the webhook daemon, binding `address` and verifying incoming push events against the
+/// pre-shared keys in `secrets_path`.
+pub async fn serve(address: &str, secrets_path: &Path, max_threads: usize) -> Result<()> {
+    let config_str = std::fs::read_to_string(secrets_path)?;
+    let config: SecretsConfig = toml::from_str(&config_str)?;
+    let secrets = config.webhooks.into_iter().map(|w| w.secret).collect();
+
+    let state = AppState {
+        secrets: Arc::new(secrets),
+        semaphore: Arc::new(Semaphore::new(max_threads)),
+    };
+
+    let app = Router::new()
+        .route("/webhook", post(handle_webhook))
+        .with_state(state);
+
+    println!("🪝 Listening for GitHub push webhooks on {address}...");
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let Some(signature) = headers
+        .get("x-hub-signature-256")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("sha256="))
+    else {
+        return StatusCode::UNAUTHORIZED;
+    };
+
+    if !verify_signature(&state.secrets, &body, signature) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let event_type = headers
+        .get("x-github-event")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+
+    if event_type != "push" {
+        // Acknowledge pings and any other event types we don't act on, so GitHub's webhook
+        // delivery isn't shown as failing.
+        return StatusCode::OK;
+    }
+
+    let Ok(event) = serde_json::from_slice::<PushEvent>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    let gh_output = GHOuput {
+        sshUrl: event.repository.ssh_url,
+        url: event
+            .repository
+            .clone_url
+            .trim_end_matches(".git")
+            .to_string(),
+    };
+
+    let Ok(repo) = Repo::try_from(&gh_output) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    println!("🪝 Push received for [{}], syncing...", repo.name);
+    tokio::spawn(process_repo(
+        state.semaphore.clone(),
+        Arc::new(AtomicUsize::new(0)),
+        Arc::new(MultiProgress::new()),
+        None,
+        Arc::new(String::new()),
+        Arc::new(std::path::PathBuf::from(".")),
+        repo,
+        1,
+    ));
+
+    StatusCode::OK
+}
+
+/// Compares `signature` (hex-encoded) against `HMAC-SHA256(secret, body)` for each configured
+/// secret in constant time, accepting the request if any secret matches.
+fn verify_signature(secrets: &[String], body: &[u8], signature: &str) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+
+    secrets.iter().any(|secret| {
+        let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+            return false;
+        };
+        mac.update(body);
+        mac.verify_slice(&signature).is_ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature_accepts_matching_secret() {
+        let body = b"{\"zen\":\"hello\"}";
+        let secret = "super-secret";
+
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        assert!(verify_signature(&[secret.to_string()], body, &signature));
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_wrong_secret() {
+        let body = b"{\"zen\":\"hello\"}";
+
+        let mut mac = HmacSha256::new_from_slice(b"correct-secret").unwrap();
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        assert!(!verify_signature(
+            &["wrong-secret".to_string()],
+            body,
+            &signature
+        ));
+    }
+
+    #[test]
+    fn test_verify_signature_tries_each_configured_secret() {
+        let body = b"{\"zen\":\"hello\"}";
+        let secret = "second-org-secret";
+
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        let secrets = vec!["first-org-secret".to_string(), secret.to_string()];
+        assert!(verify_signature(&secrets, body, &signature));
+    }
+}